@@ -0,0 +1,108 @@
+use serde::Deserialize;
+
+use crate::retry::RetrySettings;
+
+#[derive(Debug, Deserialize)]
+pub struct PaymentSettings {
+    #[serde(default = "default_check_interval")]
+    pub check_interval: u64,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+pub fn default_check_interval() -> u64 {
+    5
+}
+pub fn default_max_attempts() -> u32 {
+    6
+}
+
+/// Which backend `PaymentProcessor` implementation to dispatch to.
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    Stripe,
+    Payu,
+}
+
+fn default_provider() -> Provider {
+    Provider::Stripe
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PayuSettings {
+    pub client_id: String,
+    pub client_secret: String,
+    pub pos_id: String,
+    #[serde(default = "default_payu_base_url")]
+    pub base_url: String,
+}
+fn default_payu_base_url() -> String {
+    "https://secure.snd.payu.com".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    #[serde(default = "default_provider")]
+    pub provider: Provider,
+    #[serde(default)]
+    pub stripe_api_key: Option<String>,
+    /// Signing secret (`whsec_...`) used to verify inbound Stripe webhooks in `listen`.
+    #[serde(default)]
+    pub stripe_webhook_secret: Option<String>,
+    #[serde(default)]
+    pub payu: Option<PayuSettings>,
+    #[serde(default)]
+    pub payment_settings: Option<PaymentSettings>,
+    /// Path to the local SQLite database used to record created/fetched
+    /// payments, refunds and customers for `reconcile`.
+    #[serde(default = "default_db_path")]
+    pub db_path: String,
+    #[serde(default)]
+    pub rates_provider: Option<RatesProviderSettings>,
+    /// Backoff/retry policy for transient failures on mutating HTTP requests
+    /// (separate from `payment_settings`, which governs status-polling cadence).
+    #[serde(default)]
+    pub retry_settings: RetrySettings,
+}
+
+fn default_db_path() -> String {
+    "stripe_testbed.sqlite3".to_string()
+}
+
+/// Historical exchange-rate endpoint used by `--report-currency`, e.g.
+/// https://api.exchangerate.host which serves `GET {base_url}/{date}?base=..&symbols=..`.
+#[derive(Debug, Deserialize)]
+pub struct RatesProviderSettings {
+    pub base_url: String,
+}
+
+impl Config {
+    pub fn settings(&self) -> PaymentSettings {
+        match &self.payment_settings {
+            Some(s) => PaymentSettings {
+                check_interval: s.check_interval,
+                max_attempts: s.max_attempts,
+            },
+            None => PaymentSettings {
+                check_interval: default_check_interval(),
+                max_attempts: default_max_attempts(),
+            },
+        }
+    }
+}
+
+pub fn load_config(path: &std::path::PathBuf) -> anyhow::Result<Config> {
+    let s = std::fs::read_to_string(path)?;
+    let mut cfg: serde_json::Value = serde_json::from_str(&s)?;
+
+    // Backward-compat: ensure payment_settings default exists for deserialization
+    if !cfg.get("payment_settings").is_some() {
+        cfg["payment_settings"] = serde_json::json!({
+            "check_interval": 5,
+            "max_attempts": 6
+        });
+    }
+
+    let cfg: Config = serde_json::from_value(cfg)?;
+    Ok(cfg)
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+/// Historical exchange-rate lookup, cached by (date, base, quote) so a run
+/// that converts several transactions on the same day only hits the network
+/// once per currency pair.
+pub struct RatesClient {
+    base_url: String,
+    client: reqwest::Client,
+    cache: Mutex<HashMap<(String, String, String), Option<f64>>>,
+}
+
+impl RatesClient {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::builder()
+                .user_agent("stripe-testbed-rust/0.1")
+                .build()
+                .expect("client"),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Look up the rate to convert one unit of `from` into `to` as it stood on
+    /// `date`, returning `None` (rather than an error) when the provider has
+    /// nothing for that date/pair so callers can fall back gracefully.
+    pub async fn rate_on(&self, date: NaiveDate, from: &str, to: &str) -> Option<f64> {
+        let key = (date.to_string(), from.to_lowercase(), to.to_lowercase());
+        if let Some(cached) = self.cache.lock().unwrap().get(&key) {
+            return *cached;
+        }
+        let rate = self.fetch(date, from, to).await;
+        self.cache.lock().unwrap().insert(key, rate);
+        rate
+    }
+
+    async fn fetch(&self, date: NaiveDate, from: &str, to: &str) -> Option<f64> {
+        let url = format!("{}/{}", self.base_url, date.format("%Y-%m-%d"));
+        let resp = self
+            .client
+            .get(&url)
+            .query(&[("base", from.to_uppercase()), ("symbols", to.to_uppercase())])
+            .send()
+            .await
+            .ok()?;
+        if !resp.status().is_success() {
+            return None;
+        }
+        let v: Value = resp.json().await.ok()?;
+        v.get("rates")
+            .and_then(|r| r.get(to.to_uppercase()))
+            .and_then(|r| r.as_f64())
+    }
+}
@@ -1,34 +1,29 @@
-use chrono::{DateTime, TimeZone, Utc};
+mod config;
+mod fx;
+mod payu;
+mod processor;
+mod retry;
+mod store;
+mod stripe;
+mod suite;
+mod webhook;
+
+use chrono::{TimeZone, Utc};
 use clap::{Parser, Subcommand};
 use colored::*;
-use serde::Deserialize;
 use serde_json::Value;
-use std::{fs, path::PathBuf};
+use std::path::PathBuf;
 
-#[derive(Debug, Deserialize)]
-struct PaymentSettings {
-    #[serde(default = "default_check_interval")]
-    check_interval: u64,
-    #[serde(default = "default_max_attempts")]
-    max_attempts: u32,
-}
-fn default_check_interval() -> u64 {
-    5
-}
-fn default_max_attempts() -> u32 {
-    6
-}
-
-#[derive(Debug, Deserialize)]
-struct Config {
-    stripe_api_key: String,
-    #[serde(default)]
-    payment_settings: Option<PaymentSettings>,
-}
+use config::{load_config, Provider};
+use fx::RatesClient;
+use payu::PayuProcessor;
+use processor::PaymentProcessor;
+use store::{PaymentIntentRow, Store};
+use stripe::StripeProcessor;
 
 #[derive(Parser, Debug)]
 #[command(name = "stripe-testbed")]
-#[command(about = "Stripe operations testbed (Rust)")]
+#[command(about = "Payment gateway operations testbed (Rust)")]
 struct Cli {
     /// Path to configuration file (default: conf/config.json)
     #[arg(long, default_value = "conf/config.json")]
@@ -48,6 +43,9 @@ enum Commands {
         /// Currency code e.g., chf, usd
         #[arg(long, default_value = "chf")]
         currency: String,
+        /// Also show the net amount converted into this currency at the day's historical rate
+        #[arg(long)]
+        report_currency: Option<String>,
     },
     /// Retrieve current balance
     Get,
@@ -77,26 +75,124 @@ enum Commands {
     PaymentDetails {
         #[arg(long, value_name = "pi_...")]
         payment_id: String,
+        /// Also show the net amount converted into this currency at the transaction's historical rate
+        #[arg(long)]
+        report_currency: Option<String>,
+    },
+    /// Start a webhook receiver that verifies and prints incoming Stripe events
+    Listen {
+        /// Port to bind the webhook HTTP server to
+        #[arg(long, default_value_t = 4242)]
+        port: u16,
+        /// Stripe webhook signing secret (whsec_...); falls back to config.stripe_webhook_secret
+        #[arg(long)]
+        signing_secret: Option<String>,
+        /// Optional file to append each verified event's raw JSON to, one per line
+        #[arg(long)]
+        log_file: Option<PathBuf>,
+    },
+    /// Re-fetch locally recorded payment intents and diff them against live gateway state
+    Reconcile,
+    /// Drive a scenario file's payments concurrently and report pass/fail per scenario
+    RunSuite {
+        /// Max number of payments in flight at once
+        #[arg(long, default_value_t = 5)]
+        concurrency: usize,
+        /// Path to a JSON scenario file (array of {name, payment_method, expected_status, amount?, currency?})
+        #[arg(long)]
+        scenarios: PathBuf,
+    },
+    /// Move funds out of the account balance via a payout
+    CreatePayout {
+        /// Amount in smallest currency unit (e.g., cents)
+        #[arg(long, default_value_t = 1000)]
+        amount: i64,
+        /// Currency code e.g., chf, usd
+        #[arg(long, default_value = "chf")]
+        currency: String,
+        /// Payout method, e.g. "standard" or "instant"
+        #[arg(long, default_value = "standard")]
+        method: String,
+        /// Shorthand for --method instant, where eligible
+        #[arg(long)]
+        instant: bool,
+    },
+    /// List recent payouts
+    ListPayouts {
+        /// Max number of items
+        #[arg(long, default_value_t = 5)]
+        limit: u32,
     },
 }
 
+fn build_processor(config: &config::Config) -> anyhow::Result<std::sync::Arc<dyn PaymentProcessor>> {
+    match config.provider {
+        Provider::Stripe => {
+            let key = config
+                .stripe_api_key
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("config.stripe_api_key is required for provider=stripe"))?;
+            Ok(std::sync::Arc::new(StripeProcessor::new(
+                key,
+                config.retry_settings.clone(),
+            )))
+        }
+        Provider::Payu => {
+            let payu = config
+                .payu
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("config.payu settings are required for provider=payu"))?;
+            Ok(std::sync::Arc::new(PayuProcessor::new(payu)))
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let config = load_config(&cli.config)?;
-    let key = config.stripe_api_key;
-    let settings = config.payment_settings.unwrap_or(PaymentSettings {
-        check_interval: default_check_interval(),
-        max_attempts: default_max_attempts(),
-    });
+
+    // `listen` doesn't talk to a gateway at all, so it doesn't need a processor.
+    if let Commands::Listen {
+        port,
+        signing_secret,
+        log_file,
+    } = cli.command
+    {
+        let secret = signing_secret
+            .or_else(|| config.stripe_webhook_secret.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!("a signing secret is required: pass --signing-secret or set stripe_webhook_secret in config")
+            })?;
+        return webhook::run(port, secret, log_file).await;
+    }
+
+    run_gateway_command(cli, config).await
+}
+
+async fn run_gateway_command(cli: Cli, config: config::Config) -> anyhow::Result<()> {
+    let settings = config.settings();
+    let store = Store::open(&config.db_path)?;
+    let processor = build_processor(&config)?;
 
     match cli.command {
-        Commands::Set { amount, currency } => {
+        Commands::Listen { .. } => unreachable!("handled before processor dispatch"),
+        Commands::Set {
+            amount,
+            currency,
+            report_currency,
+        } => {
             println!(
                 "{}",
                 format!("Creating a payment of {} {}...", amount, currency).bold()
             );
-            let pi = create_payment(&key, amount, &currency, &settings).await?;
+            let pi = processor
+                .create_payment(amount, &currency, "pm_card_visa", &settings, false)
+                .await?;
+            store.upsert_payment_intent(&pi)?;
+            if let Some(report_currency) = report_currency {
+                print_fx_conversion(&pi, &report_currency, &config).await;
+            }
             print_disclaimer();
             if let Some(id) = pi.get("id").and_then(|v| v.as_str()) {
                 println!("\nPayment Intent id: {}", id);
@@ -104,11 +200,16 @@ async fn main() -> anyhow::Result<()> {
         }
         Commands::Get => {
             println!("Retrieving current balance...");
-            get_balance(&key).await?;
+            processor.balance().await?;
             print_disclaimer();
         }
         Commands::ListPayments { limit } => {
-            list_payments(&key, limit).await?;
+            let res = processor.list_payments(limit).await?;
+            if let Some(arr) = res.get("data").and_then(|v| v.as_array()) {
+                for pi in arr {
+                    store.upsert_payment_intent(pi)?;
+                }
+            }
             print_disclaimer();
         }
         Commands::CreateCustomer {
@@ -116,447 +217,176 @@ async fn main() -> anyhow::Result<()> {
             name,
             description,
         } => {
-            create_customer(&key, &email, &name, description.as_deref()).await?;
+            let customer = processor
+                .create_customer(&email, &name, description.as_deref())
+                .await?;
+            store.upsert_customer(&customer)?;
             print_disclaimer();
         }
         Commands::CreateRefund { payment_id } => {
-            create_refund(&key, &payment_id).await?;
+            let refund = processor.refund(&payment_id).await?;
+            store.upsert_refund(&refund)?;
             print_disclaimer();
         }
         Commands::ListMethods => {
-            list_payment_methods(&key).await?;
+            processor.list_payment_methods().await?;
             print_disclaimer();
         }
-        Commands::PaymentDetails { payment_id } => {
-            payment_details(&key, &payment_id).await?;
+        Commands::PaymentDetails {
+            payment_id,
+            report_currency,
+        } => {
+            let pi = processor.payment_details(&payment_id).await?;
+            store.upsert_payment_intent(&pi)?;
+            if let Some(report_currency) = report_currency {
+                print_fx_conversion(&pi, &report_currency, &config).await;
+            }
             print_disclaimer();
         }
-    }
-
-    Ok(())
-}
-
-fn load_config(path: &PathBuf) -> anyhow::Result<Config> {
-    let s = fs::read_to_string(path)?;
-    let mut cfg: Value = serde_json::from_str(&s)?;
-
-    // Backward-compat: ensure payment_settings default exists for deserialization
-    if !cfg.get("payment_settings").is_some() {
-        cfg["payment_settings"] = serde_json::json!({
-            "check_interval": 5,
-            "max_attempts": 6
-        });
-    }
-
-    let cfg: Config = serde_json::from_value(cfg)?;
-    Ok(cfg)
-}
-
-fn client(_key: &str) -> reqwest::Client {
-    reqwest::Client::builder()
-        .user_agent("stripe-testbed-rust/0.1")
-        .build()
-        .expect("client")
-}
-
-async fn post_form(key: &str, path: &str, form: &[(String, String)]) -> anyhow::Result<Value> {
-    let url = format!("https://api.stripe.com/v1{}", path);
-    let resp = client(key)
-        .post(&url)
-        .basic_auth(key, Some(""))
-        .form(&form)
-        .send()
-        .await?;
-    let status = resp.status();
-    let text = resp.text().await?;
-    if !status.is_success() {
-        anyhow::bail!("Stripe error {}: {}", status, text);
-    }
-    let v: Value = serde_json::from_str(&text)?;
-    Ok(v)
-}
-
-async fn get_query(key: &str, path: &str, query: &[(String, String)]) -> anyhow::Result<Value> {
-    let url = format!("https://api.stripe.com/v1{}", path);
-    let resp = client(key)
-        .get(&url)
-        .basic_auth(key, Some(""))
-        .query(&query)
-        .send()
-        .await?;
-    let status = resp.status();
-    let text = resp.text().await?;
-    if !status.is_success() {
-        anyhow::bail!("Stripe error {}: {}", status, text);
-    }
-    let v: Value = serde_json::from_str(&text)?;
-    Ok(v)
-}
-
-async fn retrieve(key: &str, path: &str, query: &[(String, String)]) -> anyhow::Result<Value> {
-    get_query(key, path, query).await
-}
-
-async fn create_payment(
-    key: &str,
-    amount: i64,
-    currency: &str,
-    settings: &PaymentSettings,
-) -> anyhow::Result<Value> {
-    // Create PaymentIntent
-    let mut form = vec![
-        ("amount".to_string(), amount.to_string()),
-        ("currency".to_string(), currency.to_string()),
-        ("confirm".to_string(), "true".to_string()),
-        ("payment_method".to_string(), "pm_card_visa".to_string()),
-    ];
-    // payment_method_types[]=card
-    form.push(("payment_method_types[]".to_string(), "card".to_string()));
-
-    let mut pi = post_form(key, "/payment_intents", &form).await?;
-
-    let initial_status = pi
-        .get("status")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown");
-    let pi_id: String = pi
-        .get("id")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string();
-    println!("Payment Intent created: {}", pi_id);
-    println!("Initial status: {}", initial_status);
-
-    println!("\nWaiting for payment confirmation...");
-    let mut attempts = 0u32;
-    while attempts < settings.max_attempts {
-        let status = pi
-            .get("status")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-        println!(
-            "Attempt {}/{} - Current status: {}",
-            attempts + 1,
-            settings.max_attempts,
-            status
-        );
-        if matches!(status, "succeeded" | "failed" | "canceled") {
-            break;
+        Commands::Reconcile => {
+            if config.provider != Provider::Stripe {
+                anyhow::bail!(
+                    "reconcile is only supported for provider=stripe: the local store only \
+                     understands Stripe's PaymentIntent JSON shape"
+                );
+            }
+            reconcile(&*processor, &store).await?;
         }
-        println!("\nWaiting for {} seconds...", settings.check_interval);
-        tokio::time::sleep(std::time::Duration::from_secs(settings.check_interval)).await;
-        attempts += 1;
-        pi = retrieve(key, &format!("/payment_intents/{}", pi_id), &[]).await?;
-    }
-
-    let final_status = pi
-        .get("status")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown");
-    println!("\nFinal status: {}", final_status);
-    if final_status != "succeeded" {
-        println!("Payment did not succeed");
-        return Ok(pi);
-    }
-
-    // Wait for balance transaction to be available
-    println!("\nWaiting for balance transaction to be available...");
-    let mut attempts = 0u32;
-    loop {
-        let expanded = retrieve(
-            key,
-            &format!("/payment_intents/{}", pi_id),
-            &[(
-                "expand[]".to_string(),
-                "latest_charge.balance_transaction".to_string(),
-            )],
-        )
-        .await?;
-        let latest_charge = expanded.get("latest_charge");
-        let bt = latest_charge.and_then(|lc| lc.get("balance_transaction"));
-        let ok = bt
-            .and_then(|b| b.get("amount"))
-            .and_then(|a| a.as_i64())
-            .is_some();
-        if ok {
-            print_transaction_details(&expanded);
-            break;
+        Commands::RunSuite {
+            concurrency,
+            scenarios,
+        } => {
+            let scenarios = suite::load_scenarios(&scenarios)?;
+            let results = suite::run(processor, scenarios, concurrency, settings).await?;
+            if results.iter().any(|r| !r.passed) {
+                std::process::exit(1);
+            }
         }
-        attempts += 1;
-        if attempts >= settings.max_attempts {
-            println!("No balance transaction available after waiting");
-            break;
+        Commands::CreatePayout {
+            amount,
+            currency,
+            method,
+            instant,
+        } => {
+            let method = if instant { "instant" } else { &method };
+            processor.create_payout(amount, &currency, method).await?;
+            print_disclaimer();
         }
-        println!(
-            "Attempt {}/{} - Waiting for balance transaction...",
-            attempts, settings.max_attempts
-        );
-        tokio::time::sleep(std::time::Duration::from_secs(settings.check_interval)).await;
-    }
-
-    Ok(pi)
-}
-
-fn print_transaction_details(pi: &Value) {
-    if let Some(ch) = pi.get("latest_charge") {
-        if let Some(bt) = ch.get("balance_transaction") {
-            let gross = bt.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
-            let fee = bt.get("fee").and_then(|v| v.as_i64()).unwrap_or(0);
-            let net = bt.get("net").and_then(|v| v.as_i64()).unwrap_or(0);
-            let cur = bt.get("currency").and_then(|v| v.as_str()).unwrap_or("");
-            println!("\nTransaction Details:");
-            println!("Gross amount: {} {}", gross, cur);
-            println!("Stripe fee  : {} {}", fee, cur);
-            println!("Net to you  : {} {}", net, cur);
-            if let Some(arr) = bt.get("fee_details").and_then(|v| v.as_array()) {
-                println!("\nFee details:");
-                for f in arr {
-                    let t = f.get("type").and_then(|v| v.as_str()).unwrap_or("");
-                    let a = f.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
-                    let c = f.get("currency").and_then(|v| v.as_str()).unwrap_or("");
-                    let d = f.get("description").and_then(|v| v.as_str()).unwrap_or("");
-                    println!(" - {:>12}  {:>5} {}  {}", t, a, c, d);
-                }
-            }
+        Commands::ListPayouts { limit } => {
+            processor.list_payouts(limit).await?;
+            print_disclaimer();
         }
     }
-}
 
-async fn get_balance(key: &str) -> anyhow::Result<()> {
-    let bal = retrieve(key, "/balance", &[]).await?;
-    println!("\nCurrent Balance:");
-    let pending = bal
-        .get("pending")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-    let available = bal
-        .get("available")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-    let p: Vec<String> = pending
-        .iter()
-        .map(|x| {
-            format!(
-                "({},{})",
-                x.get("currency").and_then(|v| v.as_str()).unwrap_or(""),
-                x.get("amount").and_then(|v| v.as_i64()).unwrap_or(0)
-            )
-        })
-        .collect();
-    let a: Vec<String> = available
-        .iter()
-        .map(|x| {
-            format!(
-                "({},{})",
-                x.get("currency").and_then(|v| v.as_str()).unwrap_or(""),
-                x.get("amount").and_then(|v| v.as_i64()).unwrap_or(0)
-            )
-        })
-        .collect();
-    println!("Pending : {}", p.join(", "));
-    println!("Available: {}", a.join(", "));
     Ok(())
 }
 
-async fn list_payments(key: &str, limit: u32) -> anyhow::Result<()> {
-    let res = retrieve(
-        key,
-        "/payment_intents",
-        &[("limit".to_string(), limit.to_string())],
-    )
-    .await?;
-    println!("\nRecent Payments:");
-    if let Some(arr) = res.get("data").and_then(|v| v.as_array()) {
-        for p in arr {
-            let id = p.get("id").and_then(|v| v.as_str()).unwrap_or("");
-            let amt = p.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
-            let cur = p.get("currency").and_then(|v| v.as_str()).unwrap_or("");
-            let st = p.get("status").and_then(|v| v.as_str()).unwrap_or("");
-            let created_ts = p.get("created").and_then(|v| v.as_i64()).unwrap_or(0);
-            let created_dt = Utc
-                .timestamp_opt(created_ts, 0)
-                .single()
-                .unwrap_or_else(Utc::now);
+/// Re-fetch every locally-stored payment intent and report any drift between
+/// the recorded status/net amount and what the gateway reports live.
+async fn reconcile(processor: &dyn PaymentProcessor, store: &Store) -> anyhow::Result<()> {
+    let local_rows = store.list_payment_intents()?;
+    println!(
+        "{}",
+        format!("Reconciling {} locally-recorded payment intent(s)...", local_rows.len()).bold()
+    );
+
+    let mut drifted = 0u32;
+    let mut missing = 0u32;
+    for local in &local_rows {
+        let live = match processor.payment_details(&local.id).await {
+            Ok(v) => v,
+            Err(e) => {
+                missing += 1;
+                println!(
+                    "{}",
+                    format!("  {} - could not be re-fetched: {}", local.id, e).red()
+                );
+                continue;
+            }
+        };
+        let live_row = PaymentIntentRow::from_json(&live);
+        if live_row.status != local.status || live_row.net != local.net {
+            drifted += 1;
             println!(
-                "ID: {}\nAmount: {} {}\nStatus: {}\n{}",
-                id,
-                amt,
-                cur,
-                st,
-                "-".repeat(40)
+                "{}",
+                format!(
+                    "  {} DRIFTED - status: {} -> {}, net: {:?} -> {:?}",
+                    local.id, local.status, live_row.status, local.net, live_row.net
+                )
+                .yellow()
             );
-            println!("Created: {}", created_dt.to_rfc3339());
+            store.upsert_payment_intent(&live)?;
+        } else {
+            println!("{}", format!("  {} OK", local.id).green());
         }
     }
-    Ok(())
-}
 
-async fn create_customer(
-    key: &str,
-    email: &str,
-    name: &str,
-    description: Option<&str>,
-) -> anyhow::Result<()> {
-    let mut form = vec![
-        ("email".to_string(), email.to_string()),
-        ("name".to_string(), name.to_string()),
-    ];
-    if let Some(d) = description {
-        form.push(("description".to_string(), d.to_string()));
-    }
-    let c = post_form(key, "/customers", &form).await?;
-    println!("\nCustomer Created:");
-    println!("ID: {}", c.get("id").and_then(|v| v.as_str()).unwrap_or(""));
     println!(
-        "Name: {}",
-        c.get("name").and_then(|v| v.as_str()).unwrap_or("")
-    );
-    println!(
-        "Email: {}",
-        c.get("email").and_then(|v| v.as_str()).unwrap_or("")
+        "\nDone: {} checked, {} drifted, {} missing/unreachable",
+        local_rows.len(),
+        drifted,
+        missing
     );
     Ok(())
 }
 
-async fn create_refund(key: &str, payment_intent_id: &str) -> anyhow::Result<()> {
-    // retrieve PI first
-    let pi = retrieve(key, &format!("/payment_intents/{}", payment_intent_id), &[]).await?;
-    let latest_charge = pi
-        .get("latest_charge")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    if latest_charge.is_empty() {
-        println!("No charge found for this payment intent");
-        return Ok(());
-    }
-    let refund = post_form(
-        key,
-        "/refunds",
-        &[
-            ("charge".to_string(), latest_charge.to_string()),
-            ("reason".to_string(), "requested_by_customer".to_string()),
-        ],
-    )
-    .await?;
-    println!("\nRefund Created:");
-    println!(
-        "ID: {}",
-        refund.get("id").and_then(|v| v.as_str()).unwrap_or("")
-    );
-    println!(
-        "Amount: {} {}",
-        refund.get("amount").and_then(|v| v.as_i64()).unwrap_or(0),
-        refund
-            .get("currency")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-    );
-    println!(
-        "Status: {}",
-        refund.get("status").and_then(|v| v.as_str()).unwrap_or("")
-    );
-    Ok(())
-}
+/// Print the `latest_charge.balance_transaction.net` of `pi` converted into
+/// `report_currency` at the historical rate for the charge's `created` date,
+/// falling back to a note when there's nothing to convert or no rate for that day.
+///
+/// `pi` must be expanded with `latest_charge.balance_transaction` (as
+/// `StripeProcessor::create_payment`/`payment_details` both return) or this
+/// always falls through to the "no balance transaction available" note.
+async fn print_fx_conversion(pi: &Value, report_currency: &str, config: &config::Config) {
+    let Some(provider) = &config.rates_provider else {
+        println!("\nNote: no rates_provider configured, skipping --report-currency conversion");
+        return;
+    };
+    let charge = pi.get("latest_charge");
+    let bt = charge.and_then(|c| c.get("balance_transaction"));
+    let (net, currency, created_ts) = match (
+        bt.and_then(|b| b.get("net")).and_then(|v| v.as_i64()),
+        bt.and_then(|b| b.get("currency")).and_then(|v| v.as_str()),
+        charge.and_then(|c| c.get("created")).and_then(|v| v.as_i64()),
+    ) {
+        (Some(net), Some(currency), Some(created_ts)) => (net, currency, created_ts),
+        _ => {
+            println!("\nNote: no balance transaction available, skipping --report-currency conversion");
+            return;
+        }
+    };
 
-async fn list_payment_methods(key: &str) -> anyhow::Result<()> {
-    // Note: On many accounts, listing payment methods requires a customer parameter.
-    // We'll attempt a global list for parity with the Python script.
-    let res = retrieve(
-        key,
-        "/payment_methods",
-        &[
-            ("type".to_string(), "card".to_string()),
-            ("limit".to_string(), "10".to_string()),
-        ],
-    )
-    .await?;
-    println!("\nAvailable Payment Methods:");
-    if let Some(arr) = res.get("data").and_then(|v| v.as_array()) {
-        for pm in arr {
-            let id = pm.get("id").and_then(|v| v.as_str()).unwrap_or("");
-            let typ = pm.get("type").and_then(|v| v.as_str()).unwrap_or("");
-            let card = pm.get("card").cloned().unwrap_or(Value::Null);
-            let brand = card.get("brand").and_then(|v| v.as_str()).unwrap_or("");
-            let last4 = card.get("last4").and_then(|v| v.as_str()).unwrap_or("");
+    let date = match Utc.timestamp_opt(created_ts, 0).single() {
+        Some(dt) => dt.date_naive(),
+        None => {
+            println!("\nNote: could not determine the transaction date, skipping --report-currency conversion");
+            return;
+        }
+    };
+
+    let rates = RatesClient::new(provider.base_url.clone());
+    match rates.rate_on(date, report_currency, currency).await {
+        Some(rate) if rate > 0.0 => {
+            let converted = net as f64 / rate;
             println!(
-                "ID: {}\nType: {}\nBrand: {}\nLast 4: {}\n{}",
-                id,
-                typ,
-                brand,
-                last4,
-                "-".repeat(40)
+                "Net to you: {} {} \u{2248} {:.2} {} @{:.4} ({})",
+                net,
+                currency,
+                converted,
+                report_currency,
+                rate,
+                date.format("%Y-%m-%d")
+            );
+        }
+        _ => {
+            println!(
+                "Note: no {}/{} exchange rate available for {}",
+                report_currency,
+                currency,
+                date.format("%Y-%m-%d")
             );
         }
     }
-    Ok(())
-}
-
-async fn payment_details(key: &str, payment_intent_id: &str) -> anyhow::Result<()> {
-    let pi = retrieve(
-        key,
-        &format!("/payment_intents/{}", payment_intent_id),
-        &[(
-            "expand[]".to_string(),
-            "latest_charge.balance_transaction".to_string(),
-        )],
-    )
-    .await?;
-
-    let id = pi.get("id").and_then(|v| v.as_str()).unwrap_or("");
-    let status = pi.get("status").and_then(|v| v.as_str()).unwrap_or("");
-    let amount = pi.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
-    let currency = pi.get("currency").and_then(|v| v.as_str()).unwrap_or("");
-    let ch = pi.get("latest_charge").cloned().unwrap_or(Value::Null);
-    if ch.is_null() {
-        println!("No charge found for this payment intent");
-        return Ok(());
-    }
-
-    let bt = ch
-        .get("balance_transaction")
-        .cloned()
-        .unwrap_or(Value::Null);
-    let available_on_ts = bt.get("available_on").and_then(|v| v.as_i64()).unwrap_or(0);
-    let created_ts = ch.get("created").and_then(|v| v.as_i64()).unwrap_or(0);
-
-    let created_dt: DateTime<Utc> = Utc
-        .timestamp_opt(created_ts, 0)
-        .single()
-        .unwrap_or_else(Utc::now);
-    let available_on_dt: DateTime<Utc> = Utc
-        .timestamp_opt(available_on_ts, 0)
-        .single()
-        .unwrap_or_else(Utc::now);
-
-    println!("\nPayment Details:");
-    println!("Payment ID: {}", id);
-    println!("Status: {}", status);
-    println!("Amount: {} {}", amount, currency);
-    println!("Transaction Date: {} (UTC)", created_dt.to_rfc3339());
-    println!("Available on: {} (UTC)", available_on_dt.to_rfc3339());
-    println!(
-        "Balance Transaction Status: {}",
-        bt.get("status").and_then(|v| v.as_str()).unwrap_or("")
-    );
-    println!(
-        "Gross amount: {} {}",
-        bt.get("amount").and_then(|v| v.as_i64()).unwrap_or(0),
-        bt.get("currency").and_then(|v| v.as_str()).unwrap_or("")
-    );
-    println!(
-        "Fee: {} {}",
-        bt.get("fee").and_then(|v| v.as_i64()).unwrap_or(0),
-        bt.get("currency").and_then(|v| v.as_str()).unwrap_or("")
-    );
-    println!(
-        "Net amount: {} {}",
-        bt.get("net").and_then(|v| v.as_i64()).unwrap_or(0),
-        bt.get("currency").and_then(|v| v.as_str()).unwrap_or("")
-    );
-
-    Ok(())
 }
 
 fn print_disclaimer() {
@@ -0,0 +1,245 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::config::PayuSettings;
+use crate::config::PaymentSettings;
+use crate::processor::PaymentProcessor;
+
+/// A second, non-Stripe backend implementing `PaymentProcessor` against PayU's
+/// REST API (https://developers.payu.com/en/restapi.html), so the CLI commands
+/// can be exercised against a different gateway shape (OAuth2 client-credentials
+/// bearer auth + JSON bodies, instead of Stripe's basic-auth + form encoding).
+pub struct PayuProcessor {
+    client_id: String,
+    client_secret: String,
+    pos_id: String,
+    base_url: String,
+}
+
+impl PayuProcessor {
+    pub fn new(settings: &PayuSettings) -> Self {
+        Self {
+            client_id: settings.client_id.clone(),
+            client_secret: settings.client_secret.clone(),
+            pos_id: settings.pos_id.clone(),
+            base_url: settings.base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .user_agent("stripe-testbed-rust/0.1")
+            .build()
+            .expect("client")
+    }
+
+    async fn access_token(&self) -> anyhow::Result<String> {
+        let url = format!("{}/pl/standard/user/oauth/authorize", self.base_url);
+        let resp = self
+            .client()
+            .post(&url)
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", &self.client_id),
+                ("client_secret", &self.client_secret),
+            ])
+            .send()
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("PayU auth error {}: {}", status, text);
+        }
+        let v: Value = serde_json::from_str(&text)?;
+        v.get("access_token")
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string())
+            .ok_or_else(|| anyhow::anyhow!("PayU auth response missing access_token: {}", text))
+    }
+
+    async fn post_json(&self, token: &str, path: &str, body: Value) -> anyhow::Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self
+            .client()
+            .post(&url)
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("PayU error {}: {}", status, text);
+        }
+        let v: Value = serde_json::from_str(&text)?;
+        Ok(v)
+    }
+
+    async fn get_json(&self, token: &str, path: &str) -> anyhow::Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self.client().get(&url).bearer_auth(token).send().await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("PayU error {}: {}", status, text);
+        }
+        let v: Value = serde_json::from_str(&text)?;
+        Ok(v)
+    }
+}
+
+#[async_trait]
+impl PaymentProcessor for PayuProcessor {
+    async fn create_payment(
+        &self,
+        amount: i64,
+        currency: &str,
+        payment_method: &str,
+        settings: &PaymentSettings,
+        quiet: bool,
+    ) -> anyhow::Result<Value> {
+        let token = self.access_token().await?;
+        let body = json!({
+            "notifyUrl": "https://example.com/payu/notify",
+            "customerIp": "127.0.0.1",
+            "merchantPosId": self.pos_id,
+            "description": "stripe-testbed order",
+            "currencyCode": currency.to_uppercase(),
+            "totalAmount": amount.to_string(),
+            "payMethods": { "payMethod": { "value": payment_method } },
+            "products": [{
+                "name": "Testbed item",
+                "unitPrice": amount.to_string(),
+                "quantity": "1"
+            }]
+        });
+        let order = self.post_json(&token, "/api/v2_1/orders", body).await?;
+        let order_id = order
+            .get("orderId")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        if !quiet {
+            println!("Order created: {}", order_id);
+            println!(
+                "Initial status: {}",
+                order
+                    .get("status")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+            );
+        }
+
+        if order_id.is_empty() {
+            return Ok(order);
+        }
+
+        if !quiet {
+            println!("\nWaiting for order confirmation...");
+        }
+        let mut attempts = 0u32;
+        let mut latest = order;
+        while attempts < settings.max_attempts {
+            let details = self
+                .get_json(&token, &format!("/api/v2_1/orders/{}", order_id))
+                .await?;
+            let status = details
+                .get("orders")
+                .and_then(|o| o.as_array())
+                .and_then(|a| a.first())
+                .and_then(|o| o.get("status"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+            if !quiet {
+                println!(
+                    "Attempt {}/{} - Current status: {}",
+                    attempts + 1,
+                    settings.max_attempts,
+                    status
+                );
+            }
+            latest = details;
+            if matches!(status.as_str(), "COMPLETED" | "CANCELED") {
+                break;
+            }
+            attempts += 1;
+            if attempts >= settings.max_attempts {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(settings.check_interval)).await;
+        }
+
+        Ok(latest)
+    }
+
+    async fn refund(&self, payment_id: &str) -> anyhow::Result<Value> {
+        let token = self.access_token().await?;
+        let refund = self
+            .post_json(
+                &token,
+                &format!("/api/v2_1/orders/{}/refunds", payment_id),
+                json!({ "refund": { "description": "requested_by_customer" } }),
+            )
+            .await?;
+        println!("\nRefund Created:");
+        println!(
+            "Status: {}",
+            refund
+                .get("status")
+                .and_then(|s| s.get("statusCode"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+        );
+        Ok(refund)
+    }
+
+    async fn list_payments(&self, _limit: u32) -> anyhow::Result<Value> {
+        // PayU's public REST API has no merchant-wide "list orders" endpoint
+        // (order history requires the separate Reports API with its own
+        // credentials), so there is nothing for this testbed to call yet.
+        anyhow::bail!("list_payments is not supported by the PayU backend")
+    }
+
+    async fn payment_details(&self, payment_id: &str) -> anyhow::Result<Value> {
+        let token = self.access_token().await?;
+        let details = self
+            .get_json(&token, &format!("/api/v2_1/orders/{}", payment_id))
+            .await?;
+        if let Some(order) = details
+            .get("orders")
+            .and_then(|o| o.as_array())
+            .and_then(|a| a.first())
+        {
+            println!("\nPayment Details:");
+            println!(
+                "Order ID: {}",
+                order.get("orderId").and_then(|v| v.as_str()).unwrap_or("")
+            );
+            println!(
+                "Status: {}",
+                order.get("status").and_then(|v| v.as_str()).unwrap_or("")
+            );
+            println!(
+                "Amount: {} {}",
+                order
+                    .get("totalAmount")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("0"),
+                order
+                    .get("currencyCode")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+            );
+        } else {
+            println!("No order found with this id");
+        }
+        Ok(details)
+    }
+
+    async fn balance(&self) -> anyhow::Result<Value> {
+        // Merchant settlement balances live in PayU's separate Reports API,
+        // not the standard Orders REST API this testbed otherwise talks to.
+        anyhow::bail!("balance is not supported by the PayU backend")
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::config::PaymentSettings;
+
+/// A payment gateway backend. Every concrete gateway (Stripe, PayU, ...) implements
+/// this so the CLI commands stay gateway-agnostic: `main` only ever talks to a
+/// `Arc<dyn PaymentProcessor>`, never to a specific provider's HTTP shape.
+#[async_trait]
+pub trait PaymentProcessor: Send + Sync {
+    /// Create a payment using the given payment-method token and, where the
+    /// provider supports polling, wait for it to settle before returning the
+    /// final representation (callers read the terminal status off the result).
+    ///
+    /// When `quiet` is true, skip the progress `println!`s (initial status,
+    /// polling attempts, transaction details, ...) - callers driving many of
+    /// these concurrently (e.g. `RunSuite`) would otherwise interleave output
+    /// from different tasks into unreadable noise.
+    async fn create_payment(
+        &self,
+        amount: i64,
+        currency: &str,
+        payment_method: &str,
+        settings: &PaymentSettings,
+        quiet: bool,
+    ) -> anyhow::Result<Value>;
+
+    /// Refund a previously created payment.
+    async fn refund(&self, payment_id: &str) -> anyhow::Result<Value>;
+
+    /// List recent payments.
+    async fn list_payments(&self, limit: u32) -> anyhow::Result<Value>;
+
+    /// Fetch the full details of a single payment.
+    async fn payment_details(&self, payment_id: &str) -> anyhow::Result<Value>;
+
+    /// Fetch the current account balance.
+    async fn balance(&self) -> anyhow::Result<Value>;
+
+    /// Create a customer record, if the provider has the concept.
+    async fn create_customer(
+        &self,
+        _email: &str,
+        _name: &str,
+        _description: Option<&str>,
+    ) -> anyhow::Result<Value> {
+        anyhow::bail!("create_customer is not supported by this provider")
+    }
+
+    /// List saved payment methods, if the provider has the concept.
+    async fn list_payment_methods(&self) -> anyhow::Result<Value> {
+        anyhow::bail!("list_payment_methods is not supported by this provider")
+    }
+
+    /// Move funds out of the account balance to the default payout destination.
+    async fn create_payout(
+        &self,
+        _amount: i64,
+        _currency: &str,
+        _method: &str,
+    ) -> anyhow::Result<Value> {
+        anyhow::bail!("create_payout is not supported by this provider")
+    }
+
+    /// List recent payouts.
+    async fn list_payouts(&self, _limit: u32) -> anyhow::Result<Value> {
+        anyhow::bail!("list_payouts is not supported by this provider")
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use rand::Rng;
+use serde::Deserialize;
+
+/// Governs retries of individual HTTP write requests on transient failure
+/// (connection reset, timeout, HTTP 5xx) - distinct from `PaymentSettings`,
+/// which governs polling a payment until it settles.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RetrySettings {
+    #[serde(default = "default_base_delay_ms")]
+    pub base_delay_ms: u64,
+    #[serde(default = "default_factor")]
+    pub factor: f64,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+fn default_base_delay_ms() -> u64 {
+    500
+}
+fn default_factor() -> f64 {
+    2.0
+}
+fn default_max_attempts() -> u32 {
+    5
+}
+fn default_max_delay_ms() -> u64 {
+    8_000
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: default_base_delay_ms(),
+            factor: default_factor(),
+            max_attempts: default_max_attempts(),
+            max_delay_ms: default_max_delay_ms(),
+        }
+    }
+}
+
+/// Exponential backoff with +/-50% jitter, capped at `max_delay_ms`, for the
+/// `attempt`'th retry (0-indexed: the first retry uses `attempt = 0`).
+pub fn backoff_delay(settings: &RetrySettings, attempt: u32) -> Duration {
+    let raw = settings.base_delay_ms as f64 * settings.factor.powi(attempt as i32);
+    let capped = raw.min(settings.max_delay_ms as f64);
+    let jitter = rand::thread_rng().gen_range(0.5..1.5);
+    Duration::from_millis((capped * jitter) as u64)
+}
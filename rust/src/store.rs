@@ -0,0 +1,199 @@
+use rusqlite::{params, Connection};
+use serde_json::Value;
+
+/// Extracted columns of a locally recorded payment intent, used for
+/// `reconcile` diffing. The full raw JSON blob Stripe returned is stored next
+/// to them in the table's `data` column but is not part of this struct.
+///
+/// This assumes Stripe's PaymentIntent shape (top-level `id`/`amount`/`currency`/
+/// `status`/`created`, with `net`/`fee` under `latest_charge.balance_transaction`).
+/// Persistence and `reconcile` are Stripe-only for now - PayU's order JSON has a
+/// different shape entirely (`orderId` instead of `id`, no balance-transaction
+/// equivalent) and isn't tracked here; see the warning `upsert_payment_intent`
+/// prints when it can't find an `id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentIntentRow {
+    pub id: String,
+    pub amount: i64,
+    pub currency: String,
+    pub status: String,
+    pub created_ts: i64,
+    pub net: Option<i64>,
+    pub fee: Option<i64>,
+}
+
+impl PaymentIntentRow {
+    /// Extract the columns we track from a raw PaymentIntent JSON value
+    /// (optionally expanded with `latest_charge.balance_transaction`).
+    pub fn from_json(pi: &Value) -> Self {
+        let bt = pi
+            .get("latest_charge")
+            .and_then(|c| c.get("balance_transaction"));
+        Self {
+            id: pi.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            amount: pi.get("amount").and_then(|v| v.as_i64()).unwrap_or(0),
+            currency: pi
+                .get("currency")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            status: pi
+                .get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            created_ts: pi.get("created").and_then(|v| v.as_i64()).unwrap_or(0),
+            net: bt.and_then(|b| b.get("net")).and_then(|v| v.as_i64()),
+            fee: bt.and_then(|b| b.get("fee")).and_then(|v| v.as_i64()),
+        }
+    }
+}
+
+/// SQLite-backed record of everything the testbed has created or fetched, so
+/// `reconcile` has a local baseline to diff the live gateway state against.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &str) -> anyhow::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS payment_intents (
+                id TEXT PRIMARY KEY,
+                amount INTEGER NOT NULL,
+                currency TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_ts INTEGER NOT NULL,
+                net INTEGER,
+                fee INTEGER,
+                data TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS refunds (
+                id TEXT PRIMARY KEY,
+                payment_intent_id TEXT,
+                amount INTEGER NOT NULL,
+                currency TEXT NOT NULL,
+                status TEXT NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS customers (
+                id TEXT PRIMARY KEY,
+                email TEXT,
+                name TEXT,
+                data TEXT NOT NULL
+            );",
+        )?;
+        Ok(Self { conn })
+    }
+
+    pub fn upsert_payment_intent(&self, pi: &Value) -> anyhow::Result<()> {
+        let row = PaymentIntentRow::from_json(pi);
+        if row.id.is_empty() {
+            eprintln!(
+                "Warning: could not find a top-level \"id\" field on this payment intent, skipping persistence \
+                 (the local store only understands Stripe's PaymentIntent shape)"
+            );
+            return Ok(());
+        }
+        self.conn.execute(
+            "INSERT INTO payment_intents (id, amount, currency, status, created_ts, net, fee, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(id) DO UPDATE SET
+                amount = excluded.amount,
+                currency = excluded.currency,
+                status = excluded.status,
+                created_ts = excluded.created_ts,
+                net = excluded.net,
+                fee = excluded.fee,
+                data = excluded.data",
+            params![
+                row.id,
+                row.amount,
+                row.currency,
+                row.status,
+                row.created_ts,
+                row.net,
+                row.fee,
+                pi.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn upsert_refund(&self, refund: &Value) -> anyhow::Result<()> {
+        let id = refund.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        if id.is_empty() {
+            eprintln!(
+                "Warning: could not find a top-level \"id\" field on this refund, skipping persistence \
+                 (the local store only understands Stripe's Refund shape)"
+            );
+            return Ok(());
+        }
+        self.conn.execute(
+            "INSERT INTO refunds (id, payment_intent_id, amount, currency, status, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                payment_intent_id = excluded.payment_intent_id,
+                amount = excluded.amount,
+                currency = excluded.currency,
+                status = excluded.status,
+                data = excluded.data",
+            params![
+                id,
+                refund.get("payment_intent").and_then(|v| v.as_str()),
+                refund.get("amount").and_then(|v| v.as_i64()).unwrap_or(0),
+                refund.get("currency").and_then(|v| v.as_str()).unwrap_or(""),
+                refund.get("status").and_then(|v| v.as_str()).unwrap_or(""),
+                refund.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn upsert_customer(&self, customer: &Value) -> anyhow::Result<()> {
+        let id = customer.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        if id.is_empty() {
+            eprintln!(
+                "Warning: could not find a top-level \"id\" field on this customer, skipping persistence \
+                 (the local store only understands Stripe's Customer shape)"
+            );
+            return Ok(());
+        }
+        self.conn.execute(
+            "INSERT INTO customers (id, email, name, data)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET
+                email = excluded.email,
+                name = excluded.name,
+                data = excluded.data",
+            params![
+                id,
+                customer.get("email").and_then(|v| v.as_str()),
+                customer.get("name").and_then(|v| v.as_str()),
+                customer.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_payment_intents(&self) -> anyhow::Result<Vec<PaymentIntentRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, amount, currency, status, created_ts, net, fee FROM payment_intents",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(PaymentIntentRow {
+                    id: row.get(0)?,
+                    amount: row.get(1)?,
+                    currency: row.get(2)?,
+                    status: row.get(3)?,
+                    created_ts: row.get(4)?,
+                    net: row.get(5)?,
+                    fee: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+}
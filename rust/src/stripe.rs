@@ -0,0 +1,565 @@
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+
+use crate::config::PaymentSettings;
+use crate::processor::PaymentProcessor;
+use crate::retry::{self, RetrySettings};
+
+pub struct StripeProcessor {
+    pub api_key: String,
+    retry_settings: RetrySettings,
+}
+
+impl StripeProcessor {
+    pub fn new(api_key: String, retry_settings: RetrySettings) -> Self {
+        Self {
+            api_key,
+            retry_settings,
+        }
+    }
+
+    fn client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .user_agent("stripe-testbed-rust/0.1")
+            .build()
+            .expect("client")
+    }
+
+    /// POST `form` to `path`, tagged with a fresh `Idempotency-Key` so that a
+    /// retried request is deduplicated by Stripe instead of creating a second
+    /// charge. Retries on connection errors/timeouts and HTTP 5xx with
+    /// exponential backoff and jitter, per `self.retry_settings`.
+    async fn post_with_retry(
+        &self,
+        path: &str,
+        form: &[(String, String)],
+    ) -> anyhow::Result<(reqwest::StatusCode, String)> {
+        let url = format!("https://api.stripe.com/v1{}", path);
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+        let mut attempt = 0u32;
+        loop {
+            let result = self
+                .client()
+                .post(&url)
+                .basic_auth(&self.api_key, Some(""))
+                .header("Idempotency-Key", &idempotency_key)
+                .form(form)
+                .send()
+                .await;
+
+            let can_retry = attempt + 1 < self.retry_settings.max_attempts;
+            match result {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if status.is_server_error() && can_retry {
+                        attempt += 1;
+                        tokio::time::sleep(retry::backoff_delay(&self.retry_settings, attempt - 1))
+                            .await;
+                        continue;
+                    }
+                    let text = resp.text().await?;
+                    return Ok((status, text));
+                }
+                Err(e) if (e.is_timeout() || e.is_connect()) && can_retry => {
+                    attempt += 1;
+                    tokio::time::sleep(retry::backoff_delay(&self.retry_settings, attempt - 1)).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    async fn post_form(&self, path: &str, form: &[(String, String)]) -> anyhow::Result<Value> {
+        let (status, text) = self.post_with_retry(path, form).await?;
+        if !status.is_success() {
+            anyhow::bail!("Stripe error {}: {}", status, text);
+        }
+        let v: Value = serde_json::from_str(&text)?;
+        Ok(v)
+    }
+
+    async fn get_query(&self, path: &str, query: &[(String, String)]) -> anyhow::Result<Value> {
+        let url = format!("https://api.stripe.com/v1{}", path);
+        let resp = self
+            .client()
+            .get(&url)
+            .basic_auth(&self.api_key, Some(""))
+            .query(&query)
+            .send()
+            .await?;
+        let status = resp.status();
+        let text = resp.text().await?;
+        if !status.is_success() {
+            anyhow::bail!("Stripe error {}: {}", status, text);
+        }
+        let v: Value = serde_json::from_str(&text)?;
+        Ok(v)
+    }
+
+    async fn retrieve(&self, path: &str, query: &[(String, String)]) -> anyhow::Result<Value> {
+        self.get_query(path, query).await
+    }
+
+    /// Create a PaymentIntent, tolerating card declines: Stripe reports those as
+    /// an HTTP error whose body still carries the now-terminal PaymentIntent
+    /// under `error.payment_intent`, which is what test cards like
+    /// `pm_card_chargeDeclined` rely on to be observable at all.
+    async fn create_intent(&self, form: &[(String, String)]) -> anyhow::Result<Value> {
+        let (status, text) = self.post_with_retry("/payment_intents", form).await?;
+        let v: Value = serde_json::from_str(&text)?;
+        if status.is_success() {
+            return Ok(v);
+        }
+        if let Some(pi) = v.get("error").and_then(|e| e.get("payment_intent")).cloned() {
+            return Ok(pi);
+        }
+        anyhow::bail!("Stripe error {}: {}", status, text);
+    }
+}
+
+#[async_trait]
+impl PaymentProcessor for StripeProcessor {
+    async fn create_payment(
+        &self,
+        amount: i64,
+        currency: &str,
+        payment_method: &str,
+        settings: &PaymentSettings,
+        quiet: bool,
+    ) -> anyhow::Result<Value> {
+        // Create PaymentIntent
+        let mut form = vec![
+            ("amount".to_string(), amount.to_string()),
+            ("currency".to_string(), currency.to_string()),
+            ("confirm".to_string(), "true".to_string()),
+            ("payment_method".to_string(), payment_method.to_string()),
+        ];
+        // payment_method_types[]=card
+        form.push(("payment_method_types[]".to_string(), "card".to_string()));
+
+        let mut pi = self.create_intent(&form).await?;
+
+        let initial_status = pi
+            .get("status")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        let pi_id: String = pi
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        if !quiet {
+            println!("Payment Intent created: {}", pi_id);
+            println!("Initial status: {}", initial_status);
+            println!("\nWaiting for payment confirmation...");
+        }
+        let mut attempts = 0u32;
+        while attempts < settings.max_attempts {
+            let status = pi
+                .get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            if !quiet {
+                println!(
+                    "Attempt {}/{} - Current status: {}",
+                    attempts + 1,
+                    settings.max_attempts,
+                    status
+                );
+            }
+            if matches!(
+                status,
+                "succeeded" | "failed" | "canceled" | "requires_payment_method" | "requires_action"
+            ) {
+                break;
+            }
+            if !quiet {
+                println!("\nWaiting for {} seconds...", settings.check_interval);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(settings.check_interval)).await;
+            attempts += 1;
+            pi = self
+                .retrieve(&format!("/payment_intents/{}", pi_id), &[])
+                .await?;
+        }
+
+        let final_status = pi
+            .get("status")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        if !quiet {
+            println!("\nFinal status: {}", final_status);
+        }
+        if final_status != "succeeded" {
+            if !quiet {
+                println!("Payment did not succeed");
+            }
+            return Ok(pi);
+        }
+
+        // Wait for balance transaction to be available
+        if !quiet {
+            println!("\nWaiting for balance transaction to be available...");
+        }
+        let mut attempts = 0u32;
+        let mut expanded = pi;
+        loop {
+            expanded = self
+                .retrieve(
+                    &format!("/payment_intents/{}", pi_id),
+                    &[(
+                        "expand[]".to_string(),
+                        "latest_charge.balance_transaction".to_string(),
+                    )],
+                )
+                .await?;
+            let latest_charge = expanded.get("latest_charge");
+            let bt = latest_charge.and_then(|lc| lc.get("balance_transaction"));
+            let ok = bt
+                .and_then(|b| b.get("amount"))
+                .and_then(|a| a.as_i64())
+                .is_some();
+            if ok {
+                if !quiet {
+                    print_transaction_details(&expanded);
+                }
+                break;
+            }
+            attempts += 1;
+            if attempts >= settings.max_attempts {
+                if !quiet {
+                    println!("No balance transaction available after waiting");
+                }
+                break;
+            }
+            if !quiet {
+                println!(
+                    "Attempt {}/{} - Waiting for balance transaction...",
+                    attempts, settings.max_attempts
+                );
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(settings.check_interval)).await;
+        }
+
+        Ok(expanded)
+    }
+
+    async fn refund(&self, payment_intent_id: &str) -> anyhow::Result<Value> {
+        let pi = self
+            .retrieve(&format!("/payment_intents/{}", payment_intent_id), &[])
+            .await?;
+        let latest_charge = pi
+            .get("latest_charge")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        if latest_charge.is_empty() {
+            println!("No charge found for this payment intent");
+            return Ok(Value::Null);
+        }
+        let refund = self
+            .post_form(
+                "/refunds",
+                &[
+                    ("charge".to_string(), latest_charge.to_string()),
+                    ("reason".to_string(), "requested_by_customer".to_string()),
+                ],
+            )
+            .await?;
+        println!("\nRefund Created:");
+        println!(
+            "ID: {}",
+            refund.get("id").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        println!(
+            "Amount: {} {}",
+            refund.get("amount").and_then(|v| v.as_i64()).unwrap_or(0),
+            refund
+                .get("currency")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+        );
+        println!(
+            "Status: {}",
+            refund.get("status").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        Ok(refund)
+    }
+
+    async fn list_payments(&self, limit: u32) -> anyhow::Result<Value> {
+        let res = self
+            .retrieve(
+                "/payment_intents",
+                &[
+                    ("limit".to_string(), limit.to_string()),
+                    (
+                        "expand[]".to_string(),
+                        "data.latest_charge.balance_transaction".to_string(),
+                    ),
+                ],
+            )
+            .await?;
+        println!("\nRecent Payments:");
+        if let Some(arr) = res.get("data").and_then(|v| v.as_array()) {
+            for p in arr {
+                let id = p.get("id").and_then(|v| v.as_str()).unwrap_or("");
+                let amt = p.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
+                let cur = p.get("currency").and_then(|v| v.as_str()).unwrap_or("");
+                let st = p.get("status").and_then(|v| v.as_str()).unwrap_or("");
+                let created_ts = p.get("created").and_then(|v| v.as_i64()).unwrap_or(0);
+                let created_dt = Utc
+                    .timestamp_opt(created_ts, 0)
+                    .single()
+                    .unwrap_or_else(Utc::now);
+                println!(
+                    "ID: {}\nAmount: {} {}\nStatus: {}\n{}",
+                    id,
+                    amt,
+                    cur,
+                    st,
+                    "-".repeat(40)
+                );
+                println!("Created: {}", created_dt.to_rfc3339());
+            }
+        }
+        Ok(res)
+    }
+
+    async fn payment_details(&self, payment_intent_id: &str) -> anyhow::Result<Value> {
+        let pi = self
+            .retrieve(
+                &format!("/payment_intents/{}", payment_intent_id),
+                &[(
+                    "expand[]".to_string(),
+                    "latest_charge.balance_transaction".to_string(),
+                )],
+            )
+            .await?;
+
+        let id = pi.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        let status = pi.get("status").and_then(|v| v.as_str()).unwrap_or("");
+        let amount = pi.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
+        let currency = pi.get("currency").and_then(|v| v.as_str()).unwrap_or("");
+        let ch = pi.get("latest_charge").cloned().unwrap_or(Value::Null);
+        if ch.is_null() {
+            println!("No charge found for this payment intent");
+            return Ok(pi);
+        }
+
+        let bt = ch
+            .get("balance_transaction")
+            .cloned()
+            .unwrap_or(Value::Null);
+        let available_on_ts = bt.get("available_on").and_then(|v| v.as_i64()).unwrap_or(0);
+        let created_ts = ch.get("created").and_then(|v| v.as_i64()).unwrap_or(0);
+
+        let created_dt = Utc
+            .timestamp_opt(created_ts, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+        let available_on_dt = Utc
+            .timestamp_opt(available_on_ts, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        println!("\nPayment Details:");
+        println!("Payment ID: {}", id);
+        println!("Status: {}", status);
+        println!("Amount: {} {}", amount, currency);
+        println!("Transaction Date: {} (UTC)", created_dt.to_rfc3339());
+        println!("Available on: {} (UTC)", available_on_dt.to_rfc3339());
+        println!(
+            "Balance Transaction Status: {}",
+            bt.get("status").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        println!(
+            "Gross amount: {} {}",
+            bt.get("amount").and_then(|v| v.as_i64()).unwrap_or(0),
+            bt.get("currency").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        println!(
+            "Fee: {} {}",
+            bt.get("fee").and_then(|v| v.as_i64()).unwrap_or(0),
+            bt.get("currency").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        println!(
+            "Net amount: {} {}",
+            bt.get("net").and_then(|v| v.as_i64()).unwrap_or(0),
+            bt.get("currency").and_then(|v| v.as_str()).unwrap_or("")
+        );
+
+        Ok(pi)
+    }
+
+    async fn balance(&self) -> anyhow::Result<Value> {
+        let bal = self.retrieve("/balance", &[]).await?;
+        println!("\nCurrent Balance:");
+        let pending = bal
+            .get("pending")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let available = bal
+            .get("available")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let p: Vec<String> = pending
+            .iter()
+            .map(|x| {
+                format!(
+                    "({},{})",
+                    x.get("currency").and_then(|v| v.as_str()).unwrap_or(""),
+                    x.get("amount").and_then(|v| v.as_i64()).unwrap_or(0)
+                )
+            })
+            .collect();
+        let a: Vec<String> = available
+            .iter()
+            .map(|x| {
+                format!(
+                    "({},{})",
+                    x.get("currency").and_then(|v| v.as_str()).unwrap_or(""),
+                    x.get("amount").and_then(|v| v.as_i64()).unwrap_or(0)
+                )
+            })
+            .collect();
+        println!("Pending : {}", p.join(", "));
+        println!("Available: {}", a.join(", "));
+        Ok(bal)
+    }
+
+    async fn create_customer(
+        &self,
+        email: &str,
+        name: &str,
+        description: Option<&str>,
+    ) -> anyhow::Result<Value> {
+        let mut form = vec![
+            ("email".to_string(), email.to_string()),
+            ("name".to_string(), name.to_string()),
+        ];
+        if let Some(d) = description {
+            form.push(("description".to_string(), d.to_string()));
+        }
+        let c = self.post_form("/customers", &form).await?;
+        println!("\nCustomer Created:");
+        println!("ID: {}", c.get("id").and_then(|v| v.as_str()).unwrap_or(""));
+        println!(
+            "Name: {}",
+            c.get("name").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        println!(
+            "Email: {}",
+            c.get("email").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        Ok(c)
+    }
+
+    async fn list_payment_methods(&self) -> anyhow::Result<Value> {
+        // Note: On many accounts, listing payment methods requires a customer parameter.
+        // We'll attempt a global list for parity with the Python script.
+        let res = self
+            .retrieve(
+                "/payment_methods",
+                &[
+                    ("type".to_string(), "card".to_string()),
+                    ("limit".to_string(), "10".to_string()),
+                ],
+            )
+            .await?;
+        println!("\nAvailable Payment Methods:");
+        if let Some(arr) = res.get("data").and_then(|v| v.as_array()) {
+            for pm in arr {
+                let id = pm.get("id").and_then(|v| v.as_str()).unwrap_or("");
+                let typ = pm.get("type").and_then(|v| v.as_str()).unwrap_or("");
+                let card = pm.get("card").cloned().unwrap_or(Value::Null);
+                let brand = card.get("brand").and_then(|v| v.as_str()).unwrap_or("");
+                let last4 = card.get("last4").and_then(|v| v.as_str()).unwrap_or("");
+                println!(
+                    "ID: {}\nType: {}\nBrand: {}\nLast 4: {}\n{}",
+                    id,
+                    typ,
+                    brand,
+                    last4,
+                    "-".repeat(40)
+                );
+            }
+        }
+        Ok(res)
+    }
+
+    async fn create_payout(&self, amount: i64, currency: &str, method: &str) -> anyhow::Result<Value> {
+        let payout = self
+            .post_form(
+                "/payouts",
+                &[
+                    ("amount".to_string(), amount.to_string()),
+                    ("currency".to_string(), currency.to_string()),
+                    ("method".to_string(), method.to_string()),
+                ],
+            )
+            .await?;
+        print_payout(&payout);
+        Ok(payout)
+    }
+
+    async fn list_payouts(&self, limit: u32) -> anyhow::Result<Value> {
+        let res = self
+            .retrieve("/payouts", &[("limit".to_string(), limit.to_string())])
+            .await?;
+        println!("\nRecent Payouts:");
+        if let Some(arr) = res.get("data").and_then(|v| v.as_array()) {
+            for p in arr {
+                print_payout(p);
+            }
+        }
+        Ok(res)
+    }
+}
+
+fn print_payout(payout: &Value) {
+    let id = payout.get("id").and_then(|v| v.as_str()).unwrap_or("");
+    let amount = payout.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
+    let currency = payout.get("currency").and_then(|v| v.as_str()).unwrap_or("");
+    let status = payout.get("status").and_then(|v| v.as_str()).unwrap_or("");
+    let arrival_ts = payout.get("arrival_date").and_then(|v| v.as_i64()).unwrap_or(0);
+    let arrival_dt = Utc
+        .timestamp_opt(arrival_ts, 0)
+        .single()
+        .unwrap_or_else(Utc::now);
+    println!(
+        "ID: {}\nAmount: {} {}\nStatus: {}\nArrival date: {}\n{}",
+        id,
+        amount,
+        currency,
+        status,
+        arrival_dt.to_rfc3339(),
+        "-".repeat(40)
+    );
+}
+
+fn print_transaction_details(pi: &Value) {
+    if let Some(ch) = pi.get("latest_charge") {
+        if let Some(bt) = ch.get("balance_transaction") {
+            let gross = bt.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
+            let fee = bt.get("fee").and_then(|v| v.as_i64()).unwrap_or(0);
+            let net = bt.get("net").and_then(|v| v.as_i64()).unwrap_or(0);
+            let cur = bt.get("currency").and_then(|v| v.as_str()).unwrap_or("");
+            println!("\nTransaction Details:");
+            println!("Gross amount: {} {}", gross, cur);
+            println!("Stripe fee  : {} {}", fee, cur);
+            println!("Net to you  : {} {}", net, cur);
+            if let Some(arr) = bt.get("fee_details").and_then(|v| v.as_array()) {
+                println!("\nFee details:");
+                for f in arr {
+                    let t = f.get("type").and_then(|v| v.as_str()).unwrap_or("");
+                    let a = f.get("amount").and_then(|v| v.as_i64()).unwrap_or(0);
+                    let c = f.get("currency").and_then(|v| v.as_str()).unwrap_or("");
+                    let d = f.get("description").and_then(|v| v.as_str()).unwrap_or("");
+                    println!(" - {:>12}  {:>5} {}  {}", t, a, c, d);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use colored::*;
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+
+use crate::config::PaymentSettings;
+use crate::processor::PaymentProcessor;
+
+/// One row of a scenario file: a payment-method token to confirm a PaymentIntent
+/// with, and the status Stripe's deterministic test cards are documented to
+/// produce for it (https://stripe.com/docs/testing).
+#[derive(Debug, Deserialize, Clone)]
+pub struct Scenario {
+    pub name: String,
+    pub payment_method: String,
+    pub expected_status: String,
+    #[serde(default = "default_amount")]
+    pub amount: i64,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+}
+fn default_amount() -> i64 {
+    1000
+}
+fn default_currency() -> String {
+    "chf".to_string()
+}
+
+pub fn load_scenarios(path: &Path) -> anyhow::Result<Vec<Scenario>> {
+    let s = std::fs::read_to_string(path)?;
+    let scenarios: Vec<Scenario> = serde_json::from_str(&s)?;
+    Ok(scenarios)
+}
+
+pub struct ScenarioResult {
+    pub name: String,
+    pub expected: String,
+    pub actual: String,
+    pub passed: bool,
+    pub elapsed_ms: u128,
+}
+
+/// Drive every scenario's `create_payment` call, at most `concurrency` at a
+/// time, and report pass/fail against each scenario's expected terminal status.
+pub async fn run(
+    processor: Arc<dyn PaymentProcessor>,
+    scenarios: Vec<Scenario>,
+    concurrency: usize,
+    settings: PaymentSettings,
+) -> anyhow::Result<Vec<ScenarioResult>> {
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut join_set = tokio::task::JoinSet::new();
+
+    for scenario in scenarios {
+        let processor = processor.clone();
+        let semaphore = semaphore.clone();
+        let settings = PaymentSettings {
+            check_interval: settings.check_interval,
+            max_attempts: settings.max_attempts,
+        };
+        join_set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            let start = Instant::now();
+            let outcome = processor
+                .create_payment(
+                    scenario.amount,
+                    &scenario.currency,
+                    &scenario.payment_method,
+                    &settings,
+                    true,
+                )
+                .await;
+            let elapsed_ms = start.elapsed().as_millis();
+            let actual = match &outcome {
+                Ok(pi) => pi
+                    .get("status")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                Err(e) => format!("error: {}", e),
+            };
+            let passed = actual == scenario.expected_status;
+            ScenarioResult {
+                name: scenario.name,
+                expected: scenario.expected_status,
+                actual,
+                passed,
+                elapsed_ms,
+            }
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(res) = join_set.join_next().await {
+        results.push(res?);
+    }
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+
+    print_report(&results);
+    Ok(results)
+}
+
+fn print_report(results: &[ScenarioResult]) {
+    println!(
+        "\n{:<30} {:<24} {:<24} {:<6} {:>10}",
+        "Scenario", "Expected", "Actual", "Pass", "Elapsed"
+    );
+    println!("{}", "-".repeat(98));
+    for r in results {
+        let line = format!(
+            "{:<30} {:<24} {:<24} {:<6} {:>8}ms",
+            r.name,
+            r.expected,
+            r.actual,
+            if r.passed { "yes" } else { "no" },
+            r.elapsed_ms
+        );
+        if r.passed {
+            println!("{}", line.green());
+        } else {
+            println!("{}", line.red());
+        }
+    }
+    let failed = results.iter().filter(|r| !r.passed).count();
+    println!(
+        "\n{}/{} scenarios passed",
+        results.len() - failed,
+        results.len()
+    );
+}
@@ -0,0 +1,264 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use colored::*;
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How far a webhook's `t=` timestamp may drift from now before we reject it
+/// as a possible replay.
+const DEFAULT_TOLERANCE_SECS: i64 = 300;
+
+struct ListenState {
+    signing_secret: String,
+    log_file: Option<Mutex<tokio::fs::File>>,
+}
+
+/// Start the webhook receiver and block until it is shut down.
+pub async fn run(port: u16, signing_secret: String, log_file: Option<PathBuf>) -> anyhow::Result<()> {
+    let log_file = match log_file {
+        Some(path) => Some(Mutex::new(
+            tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await?,
+        )),
+        None => None,
+    };
+    let state = Arc::new(ListenState {
+        signing_secret,
+        log_file,
+    });
+
+    let app = Router::new()
+        .route("/webhook", post(handle_webhook))
+        .with_state(state);
+
+    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+    println!(
+        "{}",
+        format!("Listening for Stripe webhooks on http://{}/webhook", addr).bold()
+    );
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn handle_webhook(
+    State(state): State<Arc<ListenState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, String) {
+    let sig_header = match headers
+        .get("Stripe-Signature")
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "missing Stripe-Signature header".to_string(),
+            )
+        }
+    };
+
+    if let Err(e) = verify_signature(
+        sig_header,
+        &body,
+        &state.signing_secret,
+        DEFAULT_TOLERANCE_SECS,
+    ) {
+        println!("{}", format!("Rejected webhook: {}", e).red());
+        return (StatusCode::BAD_REQUEST, format!("signature verification failed: {}", e));
+    }
+
+    let event: Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => return (StatusCode::BAD_REQUEST, format!("invalid JSON: {}", e)),
+    };
+
+    print_event(&event);
+
+    if let Some(log_file) = &state.log_file {
+        let mut f = log_file.lock().await;
+        if let Err(e) = f.write_all(&body).await {
+            eprintln!("Failed to persist event: {}", e);
+        } else {
+            let _ = f.write_all(b"\n").await;
+        }
+    }
+
+    (StatusCode::OK, "ok".to_string())
+}
+
+/// Verify a `Stripe-Signature` header of the form `t=<unix_ts>,v1=<hex>[,v1=<hex>...]`
+/// against the raw request body, per Stripe's webhook signing scheme.
+fn verify_signature(
+    header: &str,
+    raw_body: &[u8],
+    secret: &str,
+    tolerance_secs: i64,
+) -> anyhow::Result<()> {
+    let mut timestamp: Option<i64> = None;
+    let mut v1_signatures = Vec::new();
+    for part in header.split(',') {
+        let mut kv = part.splitn(2, '=');
+        let key = kv.next().unwrap_or("");
+        let value = kv.next().unwrap_or("");
+        match key {
+            "t" => timestamp = value.parse::<i64>().ok(),
+            "v1" => v1_signatures.push(value),
+            _ => {}
+        }
+    }
+    let t = timestamp.ok_or_else(|| anyhow::anyhow!("missing t= in Stripe-Signature header"))?;
+    if v1_signatures.is_empty() {
+        anyhow::bail!("missing v1= signature in Stripe-Signature header");
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    if (now - t).abs() > tolerance_secs {
+        anyhow::bail!(
+            "timestamp {} outside the {}s tolerance (possible replay)",
+            t,
+            tolerance_secs
+        );
+    }
+
+    let mut signed_payload = t.to_string().into_bytes();
+    signed_payload.push(b'.');
+    signed_payload.extend_from_slice(raw_body);
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())?;
+    mac.update(&signed_payload);
+    let expected = hex::encode(mac.finalize().into_bytes());
+
+    let matched = v1_signatures
+        .iter()
+        .any(|sig| constant_time_eq(sig.as_bytes(), expected.as_bytes()));
+    if !matched {
+        anyhow::bail!("no v1 signature matched the computed HMAC");
+    }
+    Ok(())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+fn print_event(event: &Value) {
+    let event_type = event
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    println!("\n{} {}", "Event received:".bold(), event_type);
+
+    let object = event.get("data").and_then(|d| d.get("object"));
+    match event_type {
+        "payment_intent.succeeded" => {
+            let id = object.and_then(|o| o.get("id")).and_then(|v| v.as_str()).unwrap_or("");
+            let amount = object.and_then(|o| o.get("amount")).and_then(|v| v.as_i64()).unwrap_or(0);
+            let currency = object.and_then(|o| o.get("currency")).and_then(|v| v.as_str()).unwrap_or("");
+            println!(
+                "{}",
+                format!("  payment_intent {} succeeded for {} {}", id, amount, currency).green()
+            );
+        }
+        "payment_intent.payment_failed" => {
+            let id = object.and_then(|o| o.get("id")).and_then(|v| v.as_str()).unwrap_or("");
+            println!("{}", format!("  payment_intent {} failed", id).red());
+        }
+        "charge.refunded" => {
+            let id = object.and_then(|o| o.get("id")).and_then(|v| v.as_str()).unwrap_or("");
+            let amount_refunded = object
+                .and_then(|o| o.get("amount_refunded"))
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            println!(
+                "{}",
+                format!("  charge {} refunded {}", id, amount_refunded).yellow()
+            );
+        }
+        other => {
+            println!("  (no special handling for {})", other);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "whsec_test_secret";
+
+    fn sign(t: i64, body: &[u8], secret: &str) -> String {
+        let mut signed_payload = t.to_string().into_bytes();
+        signed_payload.push(b'.');
+        signed_payload.extend_from_slice(body);
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(&signed_payload);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn valid_signature_passes() {
+        let body = br#"{"type":"payment_intent.succeeded"}"#;
+        let t = chrono::Utc::now().timestamp();
+        let sig = sign(t, body, SECRET);
+        let header = format!("t={},v1={}", t, sig);
+        assert!(verify_signature(&header, body, SECRET, DEFAULT_TOLERANCE_SECS).is_ok());
+    }
+
+    #[test]
+    fn tampered_body_is_rejected() {
+        let signed_body = br#"{"type":"payment_intent.succeeded"}"#;
+        let tampered_body = br#"{"type":"payment_intent.succeeded","amount":999999}"#;
+        let t = chrono::Utc::now().timestamp();
+        let sig = sign(t, signed_body, SECRET);
+        let header = format!("t={},v1={}", t, sig);
+        assert!(verify_signature(&header, tampered_body, SECRET, DEFAULT_TOLERANCE_SECS).is_err());
+    }
+
+    #[test]
+    fn stale_timestamp_outside_tolerance_is_rejected() {
+        let body = br#"{"type":"payment_intent.succeeded"}"#;
+        let t = chrono::Utc::now().timestamp() - (DEFAULT_TOLERANCE_SECS + 60);
+        let sig = sign(t, body, SECRET);
+        let header = format!("t={},v1={}", t, sig);
+        assert!(verify_signature(&header, body, SECRET, DEFAULT_TOLERANCE_SECS).is_err());
+    }
+
+    #[test]
+    fn one_matching_v1_among_several_still_passes() {
+        let body = br#"{"type":"charge.refunded"}"#;
+        let t = chrono::Utc::now().timestamp();
+        let real_sig = sign(t, body, SECRET);
+        let header = format!("t={},v1=deadbeefdeadbeef,v1={},v1=0000000000", t, real_sig);
+        assert!(verify_signature(&header, body, SECRET, DEFAULT_TOLERANCE_SECS).is_ok());
+    }
+
+    #[test]
+    fn constant_time_eq_rejects_different_lengths() {
+        assert!(!constant_time_eq(b"abc", b"abcd"));
+    }
+}